@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use crate::hash::{hex, md5, sha256};
+use crate::http_client::{find_header, split_unquoted};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthChallenge {
+    pub scheme: String,
+    pub params: HashMap<String, String>,
+    pub token68: Option<String>,
+}
+
+impl AuthChallenge {
+    pub fn param(&self, name: &str) -> Option<&str> {
+        find_header(&self.params, name)
+    }
+}
+
+// Challenges and their parameters share the comma as a separator, so a new challenge is
+// recognised by a leading scheme token that isn't itself a `name=value` pair.
+pub(crate) fn parse_challenges(header: &str) -> Vec<AuthChallenge> {
+    let mut challenges: Vec<AuthChallenge> = Vec::new();
+
+    for item in split_unquoted(header, ',') {
+        let item = item.trim();
+        let starts_challenge = match item.find([' ', '=']) {
+            Some(pos) => item[pos..].starts_with(' ') && !item[pos..].trim_start().starts_with('='),
+            None => true,
+        };
+
+        let param = if starts_challenge {
+            let (scheme, rest) = item.split_once(' ').unwrap_or((item, ""));
+            challenges.push(AuthChallenge { scheme: scheme.to_string(), params: HashMap::new(), token68: None });
+            rest.trim()
+        } else {
+            item
+        };
+
+        let challenge = match challenges.last_mut() {
+            Some(challenge) => challenge,
+            None => continue,
+        };
+
+        // Schemes like Negotiate carry a bare token68 instead of parameters.
+        match param.split_once('=') {
+            Some((name, value)) if !value.trim_start_matches('=').is_empty() => {
+                challenge.params.insert(name.trim().to_string(), unquote(value.trim()));
+            }
+            _ if !param.is_empty() => challenge.token68 = Some(param.to_string()),
+            _ => {}
+        }
+    }
+
+    challenges
+}
+
+fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+        Some(quoted) => {
+            let mut unescaped = String::with_capacity(quoted.len());
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => unescaped.extend(chars.next()),
+                    c => unescaped.push(c),
+                }
+            }
+            unescaped
+        }
+        None => value.to_string(),
+    }
+}
+
+// Builds an `Authorization: Digest` value (RFC 7616), or None for algorithms and qops we can't answer.
+pub(crate) fn digest_authorization(
+    challenge: &AuthChallenge,
+    username: &str,
+    password: &str,
+    method: &str,
+    uri: &str,
+    nonce_count: u32,
+    cnonce: &str,
+) -> Option<String> {
+    let realm = challenge.param("realm").unwrap_or("");
+    let nonce = challenge.param("nonce")?;
+    let algorithm = challenge.param("algorithm").unwrap_or("MD5");
+
+    let (hash, session): (fn(&[u8]) -> String, bool) = match algorithm.to_ascii_uppercase().as_str() {
+        "MD5" => (|data| hex(&md5(data)), false),
+        "MD5-SESS" => (|data| hex(&md5(data)), true),
+        "SHA-256" => (|data| hex(&sha256(data)), false),
+        "SHA-256-SESS" => (|data| hex(&sha256(data)), true),
+        _ => return None,
+    };
+
+    let qop = match challenge.param("qop") {
+        Some(qops) if qops.split(',').any(|qop| qop.trim().eq_ignore_ascii_case("auth")) => Some("auth"),
+        Some(_) => return None,
+        None => None,
+    };
+
+    let mut ha1 = hash(format!("{}:{}:{}", username, realm, password).as_bytes());
+    if session {
+        ha1 = hash(format!("{}:{}:{}", ha1, nonce, cnonce).as_bytes());
+    }
+    let ha2 = hash(format!("{}:{}", method, uri).as_bytes());
+    let nc = format!("{:08x}", nonce_count);
+
+    let response = match qop {
+        Some(qop) => hash(format!("{}:{}:{}:{}:{}:{}", ha1, nonce, nc, cnonce, qop, ha2).as_bytes()),
+        None => hash(format!("{}:{}:{}", ha1, nonce, ha2).as_bytes()),
+    };
+
+    let mut authorization = format!(
+        "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+        username, realm, nonce, uri, algorithm, response
+    );
+    if let Some(qop) = qop {
+        authorization.push_str(&format!(", qop={}, nc={}, cnonce=\"{}\"", qop, nc, cnonce));
+    }
+    if let Some(opaque) = challenge.param("opaque") {
+        authorization.push_str(&format!(", opaque=\"{}\"", opaque));
+    }
+
+    Some(authorization)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_digest_challenge() {
+        let challenges = parse_challenges(
+            r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+        );
+
+        assert_eq!(challenges.len(), 1);
+        let digest = &challenges[0];
+        assert_eq!(digest.scheme, "Digest");
+        assert_eq!(digest.param("realm"), Some("http-auth@example.org"));
+        assert_eq!(digest.param("NONCE"), Some("7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v"));
+        assert_eq!(digest.param("qop"), Some("auth, auth-int"));
+    }
+
+    #[test]
+    fn splits_several_challenges() {
+        let challenges = parse_challenges(r#"Negotiate abc==, Basic realm="a, b", Digest realm="x", nonce="n""#);
+
+        assert_eq!(challenges.iter().map(|challenge| challenge.scheme.as_str()).collect::<Vec<_>>(), ["Negotiate", "Basic", "Digest"]);
+        assert_eq!(challenges[0].token68.as_deref(), Some("abc=="));
+        assert_eq!(challenges[1].param("realm"), Some("a, b"));
+        assert_eq!(challenges[2].param("nonce"), Some("n"));
+    }
+
+    #[test]
+    fn digest_authorization_matches_the_rfc_7616_example() {
+        let challenge = |algorithm: &str| parse_challenges(&format!(
+            r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm={}, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+            algorithm,
+        )).remove(0);
+        let authorize = |algorithm: &str| digest_authorization(
+            &challenge(algorithm), "Mufasa", "Circle of Life", "GET", "/dir/index.html", 1, "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+        ).unwrap();
+
+        let md5 = authorize("MD5");
+        assert!(md5.contains("response=\"8ca523f5e9506fed4657c9700eebdbec\""));
+        assert!(md5.contains("qop=auth, nc=00000001") && md5.contains("opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\""));
+        assert!(authorize("SHA-256").contains("response=\"753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1\""));
+        assert_eq!(digest_authorization(&challenge("SHA-512"), "u", "p", "GET", "/", 1, "c"), None);
+    }
+}
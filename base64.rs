@@ -0,0 +1,79 @@
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn encode(input: &[u8]) -> String {
+    let mut output = String::with_capacity(input.len().div_ceil(3) * 4);
+
+    for chunk in input.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let group = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                output.push(ALPHABET[(group >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                output.push('=');
+            }
+        }
+    }
+
+    output
+}
+
+// Padding is optional, but when present it has to complete the last group; leftover bits must be zero.
+pub fn decode(input: &str) -> Option<Vec<u8>> {
+    let padded = input.trim();
+    let input = padded.strip_suffix("==").or_else(|| padded.strip_suffix('=')).unwrap_or(padded);
+    if input.len() % 4 == 1 || (input.len() != padded.len() && !padded.len().is_multiple_of(4)) {
+        return None;
+    }
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for byte in input.bytes() {
+        let value = ALPHABET.iter().position(|&symbol| symbol == byte)? as u32;
+        buffer = buffer << 6 | value;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    if buffer != 0 {
+        return None;
+    }
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_with_padding() {
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg==");
+        assert_eq!(encode(b"fo"), "Zm8=");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn decodes_padded_and_unpadded() {
+        assert_eq!(decode("Zm9vYmFy").unwrap(), b"foobar");
+        assert_eq!(decode("Zg==").unwrap(), b"f");
+        assert_eq!(decode("Zm8=").unwrap(), b"fo");
+        assert_eq!(decode("Zm8").unwrap(), b"fo");
+        assert_eq!(decode("").unwrap(), b"");
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for malformed in ["Z", "Zm9vY", "Zh==", "Zm9=", "Zg=", "Zg===", "Z=g=", "=Zg=", "Zm9v!", "Zm8=Zm8="] {
+            assert_eq!(decode(malformed), None, "{}", malformed);
+        }
+    }
+}
@@ -2,7 +2,15 @@ use serde_json::{json, Value};
 use crate::http_client::HttpClient;
 use crate::http_client::HttpMethod::{Delete, Get, Post};
 
+mod auth;
+mod base64;
+mod hash;
 mod http_client;
+mod http_date;
+mod multipart;
+mod sse;
+mod stats;
+mod url_builder;
 
 fn main() {
     let url: &str = "https://jsonplaceholder.typicode.com/posts/2";
@@ -17,7 +25,7 @@ fn main() {
     });
 
     match HttpClient::request(Post, post_url, Some(&json_data)) {
-        Ok(Some(http_response)) => {
+        Ok(http_response) => {
             if http_response.status_code == 201 {
                 println!("Post successful (Status: 201 Created)");
                 println!("Response JSON body:\n{}", http_response.json_body);
@@ -25,9 +33,6 @@ fn main() {
                 println!("Unexpected response: {} {}", http_response.status_code, http_response.status_text);
             }
         }
-        Ok(None) => {
-            println!("Request was successful, but no response received");
-        }
         Err(err) => {
             eprintln!("Request failed: {:?}", err);
             std::process::exit(1);
@@ -36,7 +41,7 @@ fn main() {
 
 
     match HttpClient::request(Delete, url, None) {
-        Ok(Some(http_response)) => {
+        Ok(http_response) => {
             if http_response.status_code == 200 {
                 println!("Delete successful (Status: 200 OK)");
             } else if http_response.status_code == 204 {
@@ -45,10 +50,6 @@ fn main() {
                 println!("Unexpected response: {} {}", http_response.status_code, http_response.status_text);
             }
         }
-        Ok(None) => {
-            eprintln!("Connection timeout or invalid URL");
-            std::process::exit(1);
-        }
         Err(err) => {
             eprintln!("Request failed: {:?}", err);
             std::process::exit(1);
@@ -56,7 +57,7 @@ fn main() {
     }
 
     match HttpClient::request(Get, url, None) {
-        Ok(Some(http_response)) => {
+        Ok(http_response) => {
             println!("Response status code: {}", http_response.status_code);
             println!("Response status text: {}", http_response.status_text);
             println!("Response JSON body:\n{}", http_response.json_body);
@@ -66,10 +67,6 @@ fn main() {
                 println!("{}: {}", name, value);
             }
         }
-        Ok(None) => {
-            eprintln!("Invalid URL");
-            std::process::exit(1);
-        }
         Err(err) => {
             eprintln!("Request failed: {:?}", err);
             std::process::exit(1);
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::http_client::{find_bytes, find_header, HttpRequestError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyPart {
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl BodyPart {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    // Parses `Content-Range: bytes 0-99/1000` into the inclusive (first, last) byte positions.
+    pub fn range(&self) -> Option<(u64, u64)> {
+        let range = self.header("Content-Range")?.trim().strip_prefix("bytes ")?;
+        let (span, _total) = range.split_once('/')?;
+        let (first, last) = span.split_once('-')?;
+        Some((first.trim().parse().ok()?, last.trim().parse().ok()?))
+    }
+}
+
+pub(crate) fn boundary(content_type: &str) -> Option<String> {
+    let (media_type, params) = content_type.split_once(';')?;
+    if !media_type.trim().to_ascii_lowercase().starts_with("multipart/") {
+        return None;
+    }
+
+    params.split(';')
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+}
+
+pub(crate) fn parse_parts(body: &[u8], boundary: &str) -> Result<Vec<BodyPart>, HttpRequestError> {
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let malformed = || HttpRequestError::MalformedResponse("invalid multipart body".to_string());
+
+    // Anything before the first delimiter is preamble and is ignored.
+    let mut rest = &body[find_bytes(body, &delimiter).ok_or_else(malformed)? + delimiter.len()..];
+    let mut parts = Vec::new();
+
+    loop {
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+
+        let line_end = find_bytes(rest, b"\n").ok_or_else(malformed)?;
+        rest = &rest[line_end + 1..];
+
+        let end = find_bytes(rest, &delimiter).ok_or_else(malformed)?;
+        let mut part = &rest[..end];
+        part = part.strip_suffix(b"\n").ok_or_else(malformed)?;
+        part = part.strip_suffix(b"\r").unwrap_or(part);
+        parts.push(parse_part(part));
+
+        rest = &rest[end + delimiter.len()..];
+    }
+}
+
+fn parse_part(part: &[u8]) -> BodyPart {
+    let mut headers = HashMap::new();
+    let mut rest = part;
+
+    loop {
+        let (line, next) = match find_bytes(rest, b"\n") {
+            Some(pos) => (&rest[..pos], &rest[pos + 1..]),
+            None => (rest, &rest[rest.len()..]),
+        };
+        rest = next;
+
+        let line = String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)).into_owned();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    BodyPart { headers, body: rest.to_vec() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_byteranges_into_parts() {
+        let body = concat!(
+            "--THIS_STRING_SEPARATES\r\n",
+            "Content-Type: application/pdf\r\n",
+            "Content-Range: bytes 500-503/8000\r\n",
+            "\r\n",
+            "abcd\r\n",
+            "--THIS_STRING_SEPARATES\r\n",
+            "Content-Type: application/pdf\r\n",
+            "Content-Range: bytes 7000-7002/8000\r\n",
+            "\r\n",
+            "xyz\r\n",
+            "--THIS_STRING_SEPARATES--\r\n",
+        );
+        let boundary = boundary("multipart/byteranges; boundary=THIS_STRING_SEPARATES").unwrap();
+
+        let parts = parse_parts(body.as_bytes(), &boundary).unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].range(), Some((500, 503)));
+        assert_eq!(parts[0].body, b"abcd");
+        assert_eq!(parts[1].range(), Some((7000, 7002)));
+        assert_eq!(parts[1].body, b"xyz");
+        assert_eq!(parts[1].header("content-type"), Some("application/pdf"));
+    }
+
+    #[test]
+    fn boundary_requires_a_multipart_type() {
+        assert_eq!(boundary("multipart/mixed; boundary=\"a b\""), Some("a b".to_string()));
+        assert_eq!(boundary("text/plain; boundary=x"), None);
+        assert!(parse_parts(b"no delimiter here", "x").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+use crate::http_client::{io_error, HttpRequestError};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub data: String,
+    pub id: Option<String>,
+    pub retry: Option<u64>,
+}
+
+pub struct EventStream {
+    pub status_code: u16,
+    pub headers: HashMap<String, String>,
+    reader: BufReader<Box<dyn Read>>,
+}
+
+impl EventStream {
+    pub(crate) fn new(status_code: u16, headers: HashMap<String, String>, body: Box<dyn Read>) -> Self {
+        EventStream { status_code, headers, reader: BufReader::new(body) }
+    }
+}
+
+impl Iterator for EventStream {
+    type Item = Result<SseEvent, HttpRequestError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut event = SseEvent::default();
+        let mut has_data = false;
+
+        loop {
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                // An event still being assembled when the stream ends is dropped, as the spec requires.
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(err) => return Some(Err(io_error(err))),
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            if line.is_empty() {
+                if has_data {
+                    return Some(Ok(event));
+                }
+                event = SseEvent::default();
+                continue;
+            }
+
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+
+            match field {
+                "event" => event.event = Some(value.to_string()),
+                "data" => {
+                    if has_data {
+                        event.data.push('\n');
+                    }
+                    event.data.push_str(value);
+                    has_data = true;
+                }
+                "id" if !value.contains('\0') => event.id = Some(value.to_string()),
+                "retry" => {
+                    if let Ok(retry) = value.parse::<u64>() {
+                        event.retry = Some(retry);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(body: &'static str) -> Vec<SseEvent> {
+        EventStream::new(200, HashMap::new(), Box::new(body.as_bytes())).map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn parses_fields_and_multiline_data() {
+        let parsed = events("event: update\ndata: one\nid: 1\n\n: comment\ndata: two\ndata:three\nretry: 5\n\n");
+        assert_eq!(parsed, [
+            SseEvent { event: Some("update".to_string()), data: "one".to_string(), id: Some("1".to_string()), retry: None },
+            SseEvent { event: None, data: "two\nthree".to_string(), id: None, retry: Some(5) },
+        ]);
+    }
+
+    #[test]
+    fn skips_events_without_data_and_drops_an_unterminated_one() {
+        let parsed = events("event: ping\n\r\ndata: kept\r\n\r\ndata: cut off");
+        assert_eq!(parsed, [SseEvent { data: "kept".to_string(), ..SseEvent::default() }]);
+    }
+}
@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+// Four buckets per power of two of microseconds, so a percentile is off by at most a quarter and
+// memory stays fixed however many requests are recorded.
+const SUB_BUCKETS: u64 = 4;
+const BUCKETS: usize = 64 * SUB_BUCKETS as usize;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsSnapshot {
+    pub count: usize,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+}
+
+pub(crate) struct StatsCollector {
+    count: AtomicU64,
+    total_nanos: AtomicU64,
+    min_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+    buckets: [AtomicU64; BUCKETS],
+}
+
+impl Default for StatsCollector {
+    fn default() -> Self {
+        StatsCollector {
+            count: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            min_nanos: AtomicU64::new(u64::MAX),
+            max_nanos: AtomicU64::new(0),
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+        }
+    }
+}
+
+impl StatsCollector {
+    pub(crate) fn record(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let micros = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+
+        self.buckets[bucket(micros)].fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.min_nanos.fetch_min(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Percentiles are the upper bound of the bucket they fall in, kept within the recorded min and max.
+    pub(crate) fn snapshot(&self) -> StatsSnapshot {
+        let count = self.count.load(Ordering::Relaxed);
+        if count == 0 {
+            return StatsSnapshot::default();
+        }
+
+        let min = Duration::from_nanos(self.min_nanos.load(Ordering::Relaxed));
+        let max = Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed));
+        let counts: Vec<u64> = self.buckets.iter().map(|bucket| bucket.load(Ordering::Relaxed)).collect();
+
+        let percentile = |p: u64| {
+            let rank = (count * p).div_ceil(100).max(1);
+            let mut seen = 0;
+            for (index, bucket_count) in counts.iter().enumerate() {
+                seen += bucket_count;
+                if seen >= rank {
+                    return Duration::from_micros(bucket_upper_bound(index)).clamp(min, max);
+                }
+            }
+            max
+        };
+
+        StatsSnapshot {
+            count: count as usize,
+            min,
+            max,
+            mean: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed) / count),
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+        }
+    }
+}
+
+fn bucket(micros: u64) -> usize {
+    if micros < SUB_BUCKETS {
+        return micros as usize;
+    }
+    let log = 63 - micros.leading_zeros() as u64;
+    let sub_bucket = (micros >> (log - 2)) & (SUB_BUCKETS - 1);
+    ((log - 1) * SUB_BUCKETS + sub_bucket) as usize
+}
+
+fn bucket_upper_bound(index: usize) -> u64 {
+    let index = index as u64;
+    if index < SUB_BUCKETS {
+        return index;
+    }
+    let log = index / SUB_BUCKETS + 1;
+    let lower = (SUB_BUCKETS + index % SUB_BUCKETS) << (log - 2);
+    lower.saturating_add((1 << (log - 2)) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_cover_their_own_values() {
+        for micros in [0, 3, 4, 7, 8, 9, 1000, 123_456, u64::MAX] {
+            let index = bucket(micros);
+            assert!(index < BUCKETS);
+            assert!(bucket_upper_bound(index) >= micros);
+            assert!(index == 0 || bucket_upper_bound(index - 1) < micros);
+        }
+    }
+
+    #[test]
+    fn snapshot_summarises_recorded_durations() {
+        let collector = StatsCollector::default();
+        assert_eq!(collector.snapshot(), StatsSnapshot::default());
+
+        for millis in 1..=100 {
+            collector.record(Duration::from_millis(millis));
+        }
+        let snapshot = collector.snapshot();
+
+        assert_eq!(snapshot.count, 100);
+        assert_eq!(snapshot.min, Duration::from_millis(1));
+        assert_eq!(snapshot.max, Duration::from_millis(100));
+        assert_eq!(snapshot.mean, Duration::from_micros(50_500));
+        for (percentile, expected) in [(snapshot.p50, 50), (snapshot.p90, 90), (snapshot.p99, 99)] {
+            let expected = Duration::from_millis(expected);
+            assert!(percentile >= expected && percentile <= expected * 5 / 4, "{:?} for {:?}", percentile, expected);
+        }
+    }
+}
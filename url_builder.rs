@@ -0,0 +1,73 @@
+use url::Url;
+
+use crate::http_client::HttpRequestError;
+
+pub struct UrlBuilder {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+    segments: Vec<String>,
+    query: Vec<(String, String)>,
+}
+
+impl UrlBuilder {
+    pub fn new(scheme: &str, host: &str) -> Self {
+        UrlBuilder { scheme: scheme.to_string(), host: host.to_string(), port: None, segments: Vec::new(), query: Vec::new() }
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    // Each segment is percent-encoded on its own, so a `/` inside it doesn't start a new one.
+    pub fn segment(mut self, segment: &str) -> Self {
+        self.segments.push(segment.to_string());
+        self
+    }
+
+    pub fn query(mut self, name: &str, value: &str) -> Self {
+        self.query.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn build(self) -> Result<Url, HttpRequestError> {
+        let invalid = |msg: &str| HttpRequestError::InvalidUrl(msg.to_string());
+
+        let mut url = Url::parse(&format!("{}://{}", self.scheme, self.host))
+            .map_err(|err| HttpRequestError::InvalidUrl(err.to_string()))?;
+
+        if self.port.is_some() {
+            url.set_port(self.port).map_err(|_| invalid("URL cannot have a port"))?;
+        }
+
+        url.path_segments_mut()
+            .map_err(|_| invalid("URL cannot have path segments"))?
+            .clear()
+            .extend(&self.segments);
+
+        if !self.query.is_empty() {
+            url.query_pairs_mut().extend_pairs(&self.query);
+        }
+
+        Ok(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_each_segment_separately() {
+        let url = UrlBuilder::new("https", "host").segment("a/b").segment("c").query("x", "1").build().unwrap();
+        assert_eq!(url.as_str(), "https://host/a%2Fb/c?x=1");
+    }
+
+    #[test]
+    fn encodes_queries_and_sets_the_port() {
+        let url = UrlBuilder::new("http", "example.com").port(8080).segment("a b").query("q", "x&y=z").build().unwrap();
+        assert_eq!(url.as_str(), "http://example.com:8080/a%20b?q=x%26y%3Dz");
+        assert!(matches!(UrlBuilder::new("http", "").build(), Err(HttpRequestError::InvalidUrl(_))));
+    }
+}